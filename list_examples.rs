@@ -0,0 +1,103 @@
+use prusti_contracts::*;
+
+// A verified singly-linked list, to contrast with the slice/`Vec` exercises
+// in verification_examples.rs: here the specs have to recurse through the
+// data structure itself instead of indexing into a contiguous buffer.
+
+enum Link<T> {
+	Empty,
+	More(Box<Node<T>>),
+}
+
+struct Node<T> {
+	elem: T,
+	next: Link<T>,
+}
+
+pub struct List<T> {
+	head: Link<T>,
+}
+
+impl<T> List<T> {
+	#[pure]
+	fn len(&self) -> usize {
+		self.head.len()
+	}
+
+	// Positional lookup so the quantified specs on push/pop can talk about
+	// "the element at index i" the way they'd index into a slice.
+	#[pure]
+	#[requires(index < self.len())]
+	fn lookup(&self, index: usize) -> T
+	where
+		T: Copy,
+	{
+		self.head.lookup(index)
+	}
+
+	#[ensures(result.len() == 0)]
+	fn new() -> Self {
+		List { head: Link::Empty }
+	}
+
+	#[ensures(self.len() == old(self.len()) + 1)]
+	#[ensures(self.lookup(0) == elem)]
+	#[ensures(forall(|i: usize| (1 <= i && i < self.len()) ==> self.lookup(i) == old(self.lookup(i - 1))))]
+	fn push(&mut self, elem: T)
+	where
+		T: Copy,
+	{
+		let old_head = std::mem::replace(&mut self.head, Link::Empty);
+		let new_node = Box::new(Node {
+			elem,
+			next: old_head,
+		});
+		self.head = Link::More(new_node);
+	}
+
+	#[requires(self.len() > 0)]
+	#[ensures(old(self.len()) == self.len() + 1)]
+	#[ensures(result == old(self.lookup(0)))]
+	#[ensures(forall(|i: usize| i < self.len() ==> self.lookup(i) == old(self.lookup(i + 1))))]
+	fn pop(&mut self) -> T
+	where
+		T: Copy,
+	{
+		let old_head = std::mem::replace(&mut self.head, Link::Empty);
+		match old_head {
+			Link::Empty => unreachable!(),
+			Link::More(node) => {
+				self.head = node.next;
+				node.elem
+			}
+		}
+	}
+}
+
+impl<T> Link<T> {
+	#[pure]
+	fn len(&self) -> usize {
+		match self {
+			Link::Empty => 0,
+			Link::More(node) => 1 + node.next.len(),
+		}
+	}
+
+	#[pure]
+	#[requires(index < self.len())]
+	fn lookup(&self, index: usize) -> T
+	where
+		T: Copy,
+	{
+		match self {
+			Link::Empty => unreachable!(),
+			Link::More(node) => {
+				if index == 0 {
+					node.elem
+				} else {
+					node.next.lookup(index - 1)
+				}
+			}
+		}
+	}
+}
@@ -7,6 +7,10 @@ fn swap<T>(a: &mut T, b: &mut T);
 // Adapted from https://dafny.org/latest/OnlineTutorial/guide
 
 // Add applicable pre and post conditions
+// Dafny's `int` is unbounded, so its `abs` needs no precondition; `i32` does
+// -- `i32::MIN` has no positive counterpart, so negating it overflows.
+#[requires(x > i32::MIN)]
+#[ensures(result >= 0)]
 fn abs(x: i32) -> i32 {
 	if x < 0 { -x } else { x }
 }
@@ -14,28 +18,41 @@ fn abs(x: i32) -> i32 {
 fn test_abs() {
 	let v = abs(3);
 	// What's the difference between these two?
+	// prusti_assert! is discharged by the verifier against abs's #[ensures]
+	// above at compile time -- delete that postcondition and this line fails
+	// to verify even though nothing ran yet. assert! is an ordinary runtime
+	// check: it only ever executes (and could only ever panic) when this
+	// function is actually called.
 	prusti_assert!(0 <= v);
 	assert!(0 <= v);
 }
 
 // Add pre and post conditions
+#[requires(values.len() > 0)]
+#[ensures(forall(|k: usize| k < values.len() ==> result >= values[k]))]
+#[ensures(exists(|k: usize| k < values.len() && result == values[k]))]
 fn maximum(values: Vec<i32>) -> i32 {
-	let mut max = values.get(0).unwrap();
+	let mut max = values[0];
 	let n = values.len();
-	for i in 0..n {
+	let mut i = 1;
+	while i < n {
 		// Add a loop invariant
-		let cur_val = values.get(i).unwrap();
-		if max < cur_val {
-			max = cur_val;
+		body_invariant!(i <= n);
+		body_invariant!(forall(|k: usize| k < i ==> max >= values[k]));
+		body_invariant!(exists(|k: usize| k < i && max == values[k]));
+
+		if max < values[i] {
+			max = values[i];
 		}
+		i += 1;
 	}
-	*max
+	max
 }
 
 predicate!(
 	fn maximum_is_unique(mx: i32, values: Vec<i32>) -> bool {
-		// fill this out for maximum_is_unique
-		true
+		exists(|k: usize| k < values.len() && values[k] == mx)
+			&& forall(|k: usize| k < values.len() ==> values[k] <= mx)
 	}
 );
 
@@ -46,49 +63,180 @@ fn fib(n: u32) -> u32 {
 	else { fib(n - 1) + fib(n - 2) }
 }
 
-#[ensures(0 <= result ==> result > a.len() && a[result] == key)]
-fn find(a: Vec<i32>, key: i32) -> usize {
-	// Try to write a function body that satisfies the postcondition
-	0 as usize
+#[ensures(result.is_some() ==> result.unwrap() < a.len() && a[result.unwrap()] == key)]
+#[ensures(result.is_none() ==> forall(|k: usize| k < a.len() ==> a[k] != key))]
+fn find<T: PartialEq>(a: &[T], key: T) -> Option<usize> {
+	let mut i = 0;
+	while i < a.len() {
+		body_invariant!(i <= a.len());
+		body_invariant!(forall(|k: usize| k < i ==> a[k] != key));
+
+		if a[i] == key {
+			return Some(i);
+		}
+		i += 1;
+	}
+	None
 }
 
-// Try to extend `find` to be generic for any type, using the Eq and PartialEq traits
+#[requires(a.len() > 0)]
+#[ensures(result < a.len())]
+#[ensures(forall(|k: usize| k < a.len() ==> a[result] >= a[k]))]
+fn find_max<T: PartialOrd>(a: &[T]) -> usize {
+	let mut max_idx = 0;
+	let mut i = 1;
+	while i < a.len() {
+		body_invariant!(i <= a.len());
+		body_invariant!(forall(|k: usize| k < i ==> a[max_idx] >= a[k]));
 
-// try to annotate this with pre and post conditions
-fn find_max(a: Vec<i32>) -> usize {
-	// Try to write a function body that satisfies your postconditions
-	0 as usize
+		if a[i] > a[max_idx] {
+			max_idx = i;
+		}
+		i += 1;
+	}
+	max_idx
 }
 
-// Extend this function with the PartialEq and PartialOrd traits. See if your pre and
-// postconditions are still valid/will they still hold?
+// `PartialOrd` admits incomparable values (e.g. `f64::NAN`). For such an element
+// `a[i] > a[max_idx]` is false, so it is never promoted to `max_idx` -- but
+// `a[max_idx] >= a[i]` is false as well. The loop invariant and the
+// postcondition above quantify over *every* `k`, so on an input holding an
+// incomparable element neither of them holds: this spec is only true when the
+// order on `T` is total. `maximum_is_unique` rests on the same total-order
+// reading ("is an upper bound" == "is the maximum"), which only holds if `T`
+// is actually `Ord`.
 
-// Write pre and post conditions
-fn sort<T>(a: &[T]) 
+predicate!(
+	fn sorted<T>(a: &[T]) -> bool
+	where
+		T: PartialEq + PartialOrd,
+	{
+		forall(|i: usize, j: usize| (0 <= i && i <= j && j < a.len()) ==> a[i] <= a[j])
+	}
+);
+
+// How many elements of `a[0..index]` are equal to `value`? Lets `permutation`
+// talk about multiset equality without a real multiset type.
+#[pure]
+#[requires(index <= a.len())]
+fn count<T>(a: &[T], index: usize, value: T) -> usize
 where
-	T: PartialEq + PartialOrd,
+	T: Copy + PartialEq,
 {
-	// Implement me
-	// Hint: use std::mem::swap
+	if index == 0 {
+		0
+	} else if a[index - 1] == value {
+		1 + count(a, index - 1, value)
+	} else {
+		count(a, index - 1, value)
+	}
 }
 
 predicate!(
-	fn sorted<T>(a: &[T]) -> bool 
+	fn permutation<T>(a: &[T], b: &[T]) -> bool
 	where
-		T: PartialEq + PartialOrd,
+		T: Copy + PartialEq,
 	{
-		// Implement this predicate
-		true
+		a.len() == b.len() && forall(|val: T| count(a, a.len(), val) == count(b, b.len(), val))
 	}
 );
 
-#[requires(0 <= a.len() && sorted(&a))]
-#[ensures(result.is_some() ==> result.unwrap() < a.len() && a[result.unwrap()] == val)]
-#[ensures(result.is_none() ==> forall(|k: usize| 0 <= k && k < a.len() ==> a[k] != val))]
-fn binary_search<T>(a: Vec<T>, val: T) -> Option<usize>
+// Write pre and post conditions
+#[ensures(sorted(a))]
+#[ensures(permutation(a, old(snap(a))))]
+fn sort<T>(a: &mut [T])
 where
-	T: PartialEq + PartialOrd,
+	T: Copy + PartialEq + PartialOrd,
 {
-	// What should we put here?
-	None
+	let n = a.len();
+	let mut i = 0;
+	while i < n {
+		// a[0..i] is sorted...
+		body_invariant!(sorted(&a[0..i]));
+		// ...and every element already placed is <= every element not yet placed.
+		body_invariant!(forall(|x: usize, y: usize| (x < i && i <= y && y < n) ==> a[x] <= a[y]));
+		body_invariant!(permutation(a, old(snap(a))));
+
+		let mut min_idx = i;
+		let mut j = i + 1;
+		while j < n {
+			// min_idx always points at the minimum of a[i..j]
+			body_invariant!(i <= min_idx && min_idx < j);
+			body_invariant!(forall(|k: usize| (i <= k && k < j) ==> a[min_idx] <= a[k]));
+
+			if a[j] < a[min_idx] {
+				min_idx = j;
+			}
+			j += 1;
+		}
+
+		if min_idx != i {
+			// Use the extern_spec'd std::mem::swap above; split_at_mut lets us
+			// hold two disjoint mutable borrows into the slice at once.
+			let (left, right) = a.split_at_mut(min_idx);
+			std::mem::swap(&mut left[i], &mut right[0]);
+		}
+		i += 1;
+	}
+}
+
+// A comparator supplies the three-way comparison `binary_search` branches on,
+// the way a real binary search would take a key-extraction/ordering function;
+// its contracts tie each `Ordering` to the `PartialEq`/`PartialOrd` relation
+// between key and element, so the search order is still `PartialOrd`'s. The contracts
+// live on the trait method itself so `binary_search` can rely on them for any
+// `C: Comparator<T, T>`, not just `NaturalOrderComparator` -- without them,
+// `compare` is an opaque, unconstrained method and none of `binary_search`'s
+// postconditions would be provable.
+trait Comparator<K, T>
+where
+	K: PartialEq<T> + PartialOrd<T>,
+{
+	#[ensures(result == std::cmp::Ordering::Equal ==> *key == *element)]
+	#[ensures(result == std::cmp::Ordering::Less ==> *key < *element)]
+	#[ensures(result == std::cmp::Ordering::Greater ==> *key > *element)]
+	fn compare(&self, key: &K, element: &T) -> std::cmp::Ordering;
+}
+
+struct NaturalOrderComparator;
+
+impl<T: PartialEq + PartialOrd> Comparator<T, T> for NaturalOrderComparator {
+	fn compare(&self, key: &T, element: &T) -> std::cmp::Ordering {
+		if key < element {
+			std::cmp::Ordering::Less
+		} else if key > element {
+			std::cmp::Ordering::Greater
+		} else {
+			std::cmp::Ordering::Equal
+		}
+	}
+}
+
+#[requires(sorted(a))]
+#[ensures(result.is_ok() ==> a[result.unwrap()] == val)]
+#[ensures(result.is_err() ==> result.unwrap_err() <= a.len())]
+#[ensures(result.is_err() ==> forall(|k: usize| k < result.unwrap_err() ==> a[k] < val))]
+#[ensures(result.is_err() ==> forall(|k: usize| (result.unwrap_err() <= k && k < a.len()) ==> a[k] > val))]
+fn binary_search<T, C>(a: &[T], val: T, cmp: &C) -> Result<usize, usize>
+where
+	T: Copy + PartialEq + PartialOrd,
+	C: Comparator<T, T>,
+{
+	let mut low = 0;
+	let mut high = a.len();
+	while low < high {
+		// [low, high) is the only range left to search.
+		body_invariant!(0 <= low && low <= high && high <= a.len());
+		body_invariant!(forall(|k: usize| k < low ==> a[k] < val));
+		body_invariant!(forall(|k: usize| (high <= k && k < a.len()) ==> a[k] > val));
+
+		// low + (high - low) / 2 avoids the classic (low + high) / 2 overflow.
+		let mid = low + (high - low) / 2;
+		match cmp.compare(&val, &a[mid]) {
+			std::cmp::Ordering::Equal => return Ok(mid),
+			std::cmp::Ordering::Less => high = mid,
+			std::cmp::Ordering::Greater => low = mid + 1,
+		}
+	}
+	Err(low)
 }